@@ -0,0 +1,78 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AttrAuthor;
+
+impl Command for AttrAuthor {
+    fn name(&self) -> &str {
+        "attr author"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr author")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Record(
+                    [
+                        ("name".into(), Type::String),
+                        ("email".into(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )])
+            .allow_variants_without_examples(true)
+            .required("name", SyntaxShape::String, "Author's name.")
+            .named("email", SyntaxShape::String, "Author's email.", None)
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for recording a custom command's author."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let email: Option<Spanned<String>> = call.get_flag(engine_state, stack, "email")?;
+        Ok(author_record(call.head, name, email).into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: Spanned<String> = call.req_const(working_set, 0)?;
+        let email: Option<Spanned<String>> = call.get_flag_const(working_set, "email")?;
+        Ok(author_record(call.head, name, email).into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Record a custom command's author",
+            example: r#"@author "Jane Doe" --email "jane@example.com"
+def greet [] { "hi" }"#,
+            result: None,
+        }]
+    }
+}
+
+fn author_record(head: Span, name: Spanned<String>, email: Option<Spanned<String>>) -> Value {
+    let mut rec = record! {
+        "name" => Value::string(name.item, name.span),
+    };
+    if let Some(email) = email {
+        rec.push("email", Value::string(email.item, email.span));
+    }
+    Value::record(rec, head)
+}
@@ -0,0 +1,69 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AttrCategory;
+
+impl Command for AttrCategory {
+    fn name(&self) -> &str {
+        "attr category"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr category")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Record([("category".into(), Type::String)].into()),
+            )])
+            .allow_variants_without_examples(true)
+            .required(
+                "category",
+                SyntaxShape::String,
+                "Category to file this custom command under.",
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for recording a custom command's help category. Returns the category as a record."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let category: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let rec = record! {
+            "category" => Value::string(category.item, category.span),
+        };
+        Ok(Value::record(rec, call.head).into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let category: Spanned<String> = call.req_const(working_set, 0)?;
+        let rec = record! {
+            "category" => Value::string(category.item, category.span),
+        };
+        Ok(Value::record(rec, call.head).into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "File a custom command under the \"math\" category",
+            example: r#"@category math
+def double [] { $in * 2 }"#,
+            result: None,
+        }]
+    }
+}
@@ -0,0 +1,108 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AttrDeprecated;
+
+impl Command for AttrDeprecated {
+    fn name(&self) -> &str {
+        "attr deprecated"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr deprecated")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Record(
+                    [
+                        ("message".into(), Type::String),
+                        ("since".into(), Type::String),
+                        ("remove_in".into(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )])
+            .allow_variants_without_examples(true)
+            .optional(
+                "message",
+                SyntaxShape::String,
+                "Message describing the deprecation.",
+            )
+            .named(
+                "since",
+                SyntaxShape::String,
+                "Version the command was deprecated in.",
+                None,
+            )
+            .named(
+                "remove-in",
+                SyntaxShape::String,
+                "Version the command is planned to be removed in.",
+                None,
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for marking a custom command as deprecated. Returns the deprecation details as a record; it does not itself emit a warning."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let message: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let since: Option<Spanned<String>> = call.get_flag(engine_state, stack, "since")?;
+        let remove_in: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "remove-in")?;
+
+        Ok(deprecated_record(call.head, message, since, remove_in).into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let message: Option<Spanned<String>> = call.opt_const(working_set, 0)?;
+        let since: Option<Spanned<String>> = call.get_flag_const(working_set, "since")?;
+        let remove_in: Option<Spanned<String>> = call.get_flag_const(working_set, "remove-in")?;
+
+        Ok(deprecated_record(call.head, message, since, remove_in).into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Deprecate a custom command in favor of a replacement",
+            example: r#"@deprecated "use `str upcase` instead" --since "0.100"
+def up [] { str upcase }"#,
+            result: None,
+        }]
+    }
+}
+
+fn deprecated_record(
+    head: Span,
+    message: Option<Spanned<String>>,
+    since: Option<Spanned<String>>,
+    remove_in: Option<Spanned<String>>,
+) -> Value {
+    let mut rec = Record::new();
+    if let Some(message) = message {
+        rec.push("message", Value::string(message.item, message.span));
+    }
+    if let Some(since) = since {
+        rec.push("since", Value::string(since.item, since.span));
+    }
+    if let Some(remove_in) = remove_in {
+        rec.push("remove_in", Value::string(remove_in.item, remove_in.span));
+    }
+    Value::record(rec, head)
+}
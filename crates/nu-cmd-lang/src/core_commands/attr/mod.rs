@@ -0,0 +1,17 @@
+//! Built-in `@attribute` commands usable above a `def`, following the convention laid out in
+//! [`nu_protocol::ast::attribute`]: each is a `Command` named `attr <name>` that takes no
+//! pipeline input and returns a record describing itself.
+
+mod author;
+mod category;
+mod deprecated;
+mod example;
+mod search_terms;
+mod version;
+
+pub use author::AttrAuthor;
+pub use category::AttrCategory;
+pub use deprecated::AttrDeprecated;
+pub use example::AttrExample;
+pub use search_terms::AttrSearchTerms;
+pub use version::AttrVersion;
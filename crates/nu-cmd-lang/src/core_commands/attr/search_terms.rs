@@ -0,0 +1,70 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::IntoValue;
+
+#[derive(Clone)]
+pub struct AttrSearchTerms;
+
+impl Command for AttrSearchTerms {
+    fn name(&self) -> &str {
+        "attr search-terms"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr search-terms")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Record([("search_terms".into(), Type::list(Type::String))].into()),
+            )])
+            .allow_variants_without_examples(true)
+            .rest(
+                "terms",
+                SyntaxShape::String,
+                "Additional search terms to record for this command.",
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for recording extra search terms for a custom command. Returns the terms as a record."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let terms: Vec<String> = call.rest(engine_state, stack, 0)?;
+        let rec = record! {
+            "search_terms" => terms.into_value(call.head),
+        };
+        Ok(Value::record(rec, call.head).into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let terms: Vec<String> = call.rest_const(working_set, 0)?;
+        let rec = record! {
+            "search_terms" => terms.into_value(call.head),
+        };
+        Ok(Value::record(rec, call.head).into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Add extra search terms to a custom command",
+            example: r#"@search-terms "text" "string"
+def to-upper [] { str upcase }"#,
+            result: None,
+        }]
+    }
+}
@@ -0,0 +1,69 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AttrVersion;
+
+impl Command for AttrVersion {
+    fn name(&self) -> &str {
+        "attr version"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr version")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Record([("version".into(), Type::String)].into()),
+            )])
+            .allow_variants_without_examples(true)
+            .required(
+                "version",
+                SyntaxShape::String,
+                "Version this command was introduced in.",
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for recording the version a custom command was introduced in."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let version: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let rec = record! {
+            "version" => Value::string(version.item, version.span),
+        };
+        Ok(Value::record(rec, call.head).into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let version: Spanned<String> = call.req_const(working_set, 0)?;
+        let rec = record! {
+            "version" => Value::string(version.item, version.span),
+        };
+        Ok(Value::record(rec, call.head).into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Record the version a custom command was introduced in",
+            example: r#"@version "0.100"
+def greet [] { "hi" }"#,
+            result: None,
+        }]
+    }
+}
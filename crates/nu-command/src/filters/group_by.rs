@@ -1,7 +1,14 @@
+use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
 use nu_engine::{command_prelude::*, ClosureEval};
 use nu_protocol::{engine::Closure, IntoValue};
 
+/// A single named aggregate, e.g. `count: { length }` in `--aggregate`.
+struct Aggregator {
+    name: String,
+    closure: Closure,
+}
+
 #[derive(Clone)]
 pub struct GroupBy;
 
@@ -22,6 +29,56 @@ impl Command for GroupBy {
                 "Return a table with \"groups\" and \"items\" columns",
                 None,
             )
+            .switch(
+                "raw-keys",
+                "Key groups by the original, typed value instead of stringifying it. \
+                With --to-table, the grouper column holds the original value.",
+                None,
+            )
+            .named(
+                "bins",
+                SyntaxShape::Int,
+                "Bucket a numeric, filesize, or datetime column into this many equal-width bins \
+                instead of one group per distinct value.",
+                None,
+            )
+            .named(
+                "every",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::Duration,
+                    SyntaxShape::Filesize,
+                    SyntaxShape::Number,
+                ]),
+                "Bucket a numeric, filesize, or datetime column into fixed-width bins of this \
+                size instead of one group per distinct value.",
+                None,
+            )
+            .named(
+                "sort-by",
+                SyntaxShape::String,
+                "Sort groups by 'key' or by 'count' instead of leaving them in first-seen order. \
+                Applies recursively to each level of nested (subgrouped) output.",
+                None,
+            )
+            .switch(
+                "reverse",
+                "Reverse the group order (applied after --sort-by, if given).",
+                None,
+            )
+            .named(
+                "take",
+                SyntaxShape::Int,
+                "Only keep the first N groups (applied after sorting).",
+                None,
+            )
+            .named(
+                "aggregate",
+                SyntaxShape::Record(vec![]),
+                "A record of named closures to compute per-group aggregates instead of \
+                collecting the raw group members. Each closure receives the group's values as \
+                a list.",
+                Some('a'),
+            )
             .rest(
                 "grouper",
                 SyntaxShape::OneOf(vec![
@@ -135,6 +192,45 @@ impl Command for GroupBy {
                         Value::test_string("false"),
                     ]),
                 })),
+            },
+            Example {
+                description: "Compute per-group aggregates instead of collecting raw members",
+                example: r#"[[type size]; [a 1] [a 2] [b 3]] | group-by type --to-table --aggregate { count: { length }, total: { get size | math sum } }"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "type" => Value::test_string("a"),
+                        "count" => Value::test_int(2),
+                        "total" => Value::test_int(3),
+                    }),
+                    Value::test_record(record! {
+                        "type" => Value::test_string("b"),
+                        "count" => Value::test_int(1),
+                        "total" => Value::test_int(3),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Keep the original value type as the grouper key instead of stringifying it",
+                example: r#"[1 2 "1" "2"] | group-by --raw-keys --to-table | get group"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_int(2),
+                    Value::test_string("1"),
+                    Value::test_string("2"),
+                ])),
+            },
+            Example {
+                description: "Bucket a numeric column into 2 equal-width bins",
+                example: r#"[[size]; [0] [1] [9] [10]] | group-by size --bins 2 --to-table | get size"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_string("0..<5"),
+                    Value::test_string("5..<10"),
+                ])),
+            },
+            Example {
+                description: "Show the largest groups first",
+                example: r#"['a' 'bb' 'a' 'bb' 'bb'] | group-by --sort-by count --reverse --take 1 --to-table | get group"#,
+                result: Some(Value::test_list(vec![Value::test_string("bb")])),
             }
         ]
     }
@@ -149,39 +245,237 @@ pub fn group_by(
     let head = call.head;
     let groupers: Vec<Value> = call.rest(engine_state, stack, 0)?;
     let to_table = call.has_flag(engine_state, stack, "to-table")?;
+    let raw_keys = call.has_flag(engine_state, stack, "raw-keys")?;
+    let bins: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "bins")?;
+    let every: Option<Value> = call.get_flag(engine_state, stack, "every")?;
+    let sort_by: Option<Spanned<String>> = call.get_flag(engine_state, stack, "sort-by")?;
+    let reverse = call.has_flag(engine_state, stack, "reverse")?;
+    let take: Option<usize> = call.get_flag(engine_state, stack, "take")?;
+    let aggregate: Option<Record> = call.get_flag(engine_state, stack, "aggregate")?;
     let config = engine_state.get_config();
 
+    let sort_by = sort_by
+        .map(|sort_by| match sort_by.item.as_str() {
+            "key" => Ok(SortBy::Key),
+            "count" => Ok(SortBy::Count),
+            _ => Err(ShellError::InvalidValue {
+                valid: "'key' or 'count'".to_string(),
+                actual: sort_by.item,
+                span: sort_by.span,
+            }),
+        })
+        .transpose()?;
+
+    let binning = match (bins, every) {
+        (Some(bins), Some(_)) => {
+            return Err(ShellError::TypeMismatch {
+                err_message: "--bins and --every cannot be used together".to_string(),
+                span: bins.span,
+            })
+        }
+        (Some(bins), None) => Some(Binning::Count(bins.item)),
+        (None, Some(every)) => Some(Binning::Width(every)),
+        (None, None) => None,
+    };
+
     let values: Vec<Value> = input.into_iter().collect();
     if values.is_empty() {
         return Ok(Value::record(Record::new(), head).into_pipeline_data());
     }
 
+    let aggregators = aggregate
+        .map(|record| {
+            record
+                .into_iter()
+                .map(|(name, value)| {
+                    let span = value.span();
+                    match value {
+                        Value::Closure { val, .. } => Ok(Aggregator {
+                            name,
+                            closure: Closure::clone(&val),
+                        }),
+                        _ => Err(ShellError::TypeMismatch {
+                            err_message: "expected a closure for each aggregate".to_string(),
+                            span,
+                        }),
+                    }
+                })
+                .collect::<Result<Vec<_>, ShellError>>()
+        })
+        .transpose()?;
+
     let mut groupers = groupers.into_iter();
 
-    let grouped = if let Some(grouper) = groupers.next() {
-        let mut groups = Grouped::new(&grouper, values, config, engine_state, stack)?;
+    let mut grouped = if let Some(grouper) = groupers.next() {
+        let mut groups = Grouped::new(
+            &grouper,
+            values,
+            raw_keys,
+            binning.as_ref(),
+            config,
+            engine_state,
+            stack,
+        )?;
         for grouper in groupers {
-            groups.subgroup(&grouper, config, engine_state, stack)?;
+            groups.subgroup(&grouper, raw_keys, config, engine_state, stack)?;
         }
         groups
     } else {
-        Grouped::empty(values, config)
+        Grouped::empty(values, raw_keys, config)
     };
 
+    if sort_by.is_some() || reverse || take.is_some() {
+        grouped.sort_and_take(sort_by, reverse, take, config);
+    }
+
     let value = if to_table {
-        grouped.into_table(head)
+        grouped.into_table(head, aggregators.as_deref(), engine_state, stack)?
     } else {
-        grouped.into_record(head)
+        grouped.into_record(head, aggregators.as_deref(), engine_state, stack)?
     };
 
     Ok(value.into_pipeline_data())
 }
 
+/// Runs each aggregator against a group's values (passed as a single list input) and combines
+/// the results into a record, e.g. `{ count: 2, total: 3 }`.
+fn run_aggregators(
+    aggregators: &[Aggregator],
+    values: &[Value],
+    head: Span,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+) -> Result<Record, ShellError> {
+    let mut record = Record::new();
+    for aggregator in aggregators {
+        let input = Value::list(values.to_vec(), head);
+        let result = ClosureEval::new(engine_state, stack, aggregator.closure.clone())
+            .run_with_value(input)?
+            .into_value(head)?;
+        record.push(aggregator.name.clone(), result);
+    }
+    Ok(record)
+}
+
+/// A group's key. By default (`Key::Str`) groups are keyed by the grouper's stringified
+/// output, matching `group-by`'s historical behavior. With `--raw-keys`, `Key::Typed` keeps
+/// the original `Value` so e.g. `1` and `"1"`, or `true` and `"true"`, land in distinct groups.
+#[derive(Clone)]
+enum Key {
+    Str(String),
+    Typed(TypedKey),
+}
+
+/// Wraps a `Value` with a hash/eq impl based on nushell's own value equality, so it can be used
+/// as an `IndexMap` key.
+#[derive(Clone)]
+struct TypedKey(Value);
+
+impl PartialEq for TypedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for TypedKey {}
+
+impl std::hash::Hash for TypedKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // `Value`'s `PartialEq` never considers values of different variants equal, so hashing
+        // the variant's discriminant alongside its rendered content keeps equal values
+        // (by nu's own value equality) hashing equal, without depending on each variant's
+        // internal field layout.
+        std::mem::discriminant(&self.0).hash(state);
+        match &self.0 {
+            Value::Float { val, .. } => val.to_bits().hash(state),
+            other => other
+                .to_abbreviated_string(&nu_protocol::Config::default())
+                .hash(state),
+        }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Key::Str(a), Key::Str(b)) => a == b,
+            (Key::Typed(a), Key::Typed(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Key {}
+
+impl std::hash::Hash for Key {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self {
+            Key::Str(s) => s.hash(state),
+            Key::Typed(v) => v.hash(state),
+        }
+    }
+}
+
+impl Key {
+    fn new(value: Value, raw_keys: bool, config: &nu_protocol::Config) -> Self {
+        if raw_keys {
+            Key::Typed(TypedKey(value))
+        } else {
+            Key::Str(value.to_abbreviated_string(config))
+        }
+    }
+
+    /// Same as `new`, but for when `value` is still needed afterward (e.g. it's also the item
+    /// being grouped, not just a derived key): only clones `value` for `Key::Typed`, where the
+    /// key must own a copy, instead of unconditionally cloning before deciding whether a clone
+    /// is even needed.
+    fn from_ref(value: &Value, raw_keys: bool, config: &nu_protocol::Config) -> Self {
+        if raw_keys {
+            Key::Typed(TypedKey(value.clone()))
+        } else {
+            Key::Str(value.to_abbreviated_string(config))
+        }
+    }
+
+    /// The value used to label this group, for `--to-table`'s grouper column or the default
+    /// record form.
+    fn into_value(self, head: Span) -> Value {
+        match self {
+            Key::Str(s) => s.into_value(head),
+            Key::Typed(TypedKey(v)) => v,
+        }
+    }
+
+    /// A display form usable as a `Record` field name, which (unlike `--to-table`'s grouper
+    /// column) can only ever hold a string.
+    fn to_label_string(&self, config: &nu_protocol::Config) -> String {
+        match self {
+            Key::Str(s) => s.clone(),
+            Key::Typed(TypedKey(v)) => v.to_abbreviated_string(config),
+        }
+    }
+
+    /// Orders keys for `--sort-by key`. `Key::Typed` (from `--raw-keys`) compares the underlying
+    /// `Value`s directly, so e.g. numeric keys sort numerically instead of lexicographically;
+    /// this is the whole point of pairing `--raw-keys` with `--sort-by key`. Values whose variants
+    /// can't be compared (nushell's `PartialOrd` returns `None`, e.g. comparing a record to a
+    /// list) fall back to comparing their stringified labels, same as `Key::Str`.
+    fn cmp_for_sort(&self, other: &Self, config: &nu_protocol::Config) -> std::cmp::Ordering {
+        match (self, other) {
+            (Key::Typed(TypedKey(a)), Key::Typed(TypedKey(b))) => a.partial_cmp(b).unwrap_or_else(
+                || self.to_label_string(config).cmp(&other.to_label_string(config)),
+            ),
+            _ => self.to_label_string(config).cmp(&other.to_label_string(config)),
+        }
+    }
+}
+
 fn group_cell_path(
     column_name: CellPath,
     values: Vec<Value>,
+    raw_keys: bool,
     config: &nu_protocol::Config,
-) -> Result<IndexMap<String, Vec<Value>>, ShellError> {
+) -> Result<IndexMap<Key, Vec<Value>>, ShellError> {
     let mut groups = IndexMap::<_, Vec<_>>::new();
 
     for value in values.into_iter() {
@@ -193,7 +487,7 @@ fn group_cell_path(
             continue; // likely the result of a failed optional access, ignore this value
         }
 
-        let key = key.to_abbreviated_string(config);
+        let key = Key::new(key, raw_keys, config);
         groups.entry(key).or_default().push(value);
     }
 
@@ -204,19 +498,150 @@ fn group_closure(
     values: Vec<Value>,
     span: Span,
     closure: Closure,
+    raw_keys: bool,
     engine_state: &EngineState,
     stack: &mut Stack,
-) -> Result<IndexMap<String, Vec<Value>>, ShellError> {
+) -> Result<IndexMap<Key, Vec<Value>>, ShellError> {
     let mut groups = IndexMap::<_, Vec<_>>::new();
     let mut closure = ClosureEval::new(engine_state, stack, closure);
     let config = engine_state.get_config();
 
     for value in values {
-        let key = closure
-            .run_with_value(value.clone())?
-            .into_value(span)?
-            .to_abbreviated_string(config);
+        let key = closure.run_with_value(value.clone())?.into_value(span)?;
+        let key = Key::new(key, raw_keys, config);
+
+        groups.entry(key).or_default().push(value);
+    }
+
+    Ok(groups)
+}
+
+/// What `--sort-by` should order groups by.
+#[derive(Clone, Copy)]
+enum SortBy {
+    Key,
+    Count,
+}
+
+/// How `--bins`/`--every` should carve up a numeric/filesize/datetime column into buckets.
+enum Binning {
+    /// `--bins N`: divide the observed range into `N` equal-width buckets.
+    Count(i64),
+    /// `--every WIDTH`: fixed-width buckets of this size.
+    Width(Value),
+}
+
+/// Reads a numeric, filesize, or datetime `Value` as an `f64` for the purposes of binning.
+fn numeric_view(value: &Value, span: Span) -> Result<f64, ShellError> {
+    match value {
+        Value::Int { val, .. } => Ok(*val as f64),
+        Value::Float { val, .. } => Ok(*val),
+        Value::Filesize { val, .. } => Ok(val.get() as f64),
+        Value::Duration { val, .. } => Ok(*val as f64),
+        Value::Date { val, .. } => Ok(val.timestamp_nanos_opt().unwrap_or_default() as f64),
+        _ => Err(ShellError::TypeMismatch {
+            err_message: "expected a numeric, filesize, or datetime value to bin on".to_string(),
+            span,
+        }),
+    }
+}
+
+/// Renders a bin boundary back into the same type as `template` so bin labels read naturally,
+/// e.g. `2024-01-01..<2024-01-08` for a datetime column rather than a raw nanosecond count.
+fn value_from_numeric(template: &Value, n: f64, span: Span) -> Value {
+    match template {
+        Value::Int { .. } => Value::int(n.round() as i64, span),
+        Value::Filesize { .. } => Value::filesize(n.round() as i64, span),
+        Value::Duration { .. } => Value::duration(n.round() as i64, span),
+        Value::Date { .. } => {
+            let nanos = n.round() as i64;
+            let secs = nanos.div_euclid(1_000_000_000);
+            let nsecs = nanos.rem_euclid(1_000_000_000) as u32;
+            let date = DateTime::<Utc>::from_timestamp(secs, nsecs)
+                .unwrap_or_default()
+                .fixed_offset();
+            Value::date(date, span)
+        }
+        _ => Value::float(n, span),
+    }
+}
+
+fn group_bins(
+    column_name: CellPath,
+    values: Vec<Value>,
+    raw_keys: bool,
+    binning: &Binning,
+    span: Span,
+    config: &nu_protocol::Config,
+) -> Result<IndexMap<Key, Vec<Value>>, ShellError> {
+    let mut numbered = Vec::with_capacity(values.len());
+    for value in values {
+        let field = value
+            .clone()
+            .follow_cell_path(&column_name.members, false)?;
+
+        if matches!(field, Value::Nothing { .. }) {
+            continue; // likely the result of a failed optional access, ignore this value
+        }
+
+        let n = numeric_view(&field, span)?;
+        numbered.push((n, field, value));
+    }
+
+    let mut groups = IndexMap::<Key, Vec<Value>>::new();
+    if numbered.is_empty() {
+        return Ok(groups);
+    }
 
+    let min = numbered.iter().map(|(n, ..)| *n).fold(f64::INFINITY, f64::min);
+    let max = numbered
+        .iter()
+        .map(|(n, ..)| *n)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let template = numbered[0].1.clone();
+
+    let (width, bin_count) = match binning {
+        Binning::Count(bins) => {
+            let bin_count = (*bins).max(1);
+            let range = max - min;
+            let width = if range <= 0.0 { 1.0 } else { range / bin_count as f64 };
+            (width, bin_count)
+        }
+        Binning::Width(every) => {
+            let width = numeric_view(every, every.span())?;
+            if width <= 0.0 {
+                return Err(ShellError::InvalidValue {
+                    valid: "a positive width".to_string(),
+                    actual: every.to_abbreviated_string(config),
+                    span: every.span(),
+                });
+            }
+            let bin_count = ((max - min) / width).floor() as i64 + 1;
+            (width, bin_count.max(1))
+        }
+    };
+
+    for (n, _field, value) in numbered {
+        let index = (((n - min) / width).floor() as i64).clamp(0, bin_count - 1);
+        let lo = min + (index as f64) * width;
+        let hi = lo + width;
+        let from = value_from_numeric(&template, lo, span);
+        let to = value_from_numeric(&template, hi, span);
+        let key = if raw_keys {
+            // With `--raw-keys`, key by the bin's typed bounds instead of its rendered label, so
+            // downstream code can compare/sort bins numerically (or by datetime) without
+            // reparsing, same motivation as `--raw-keys` elsewhere in this command.
+            Key::Typed(TypedKey(Value::record(
+                record! { "from" => from, "to" => to },
+                span,
+            )))
+        } else {
+            Key::Str(format!(
+                "{}..<{}",
+                from.to_abbreviated_string(config),
+                to.to_abbreviated_string(config),
+            ))
+        };
         groups.entry(key).or_default().push(value);
     }
 
@@ -229,16 +654,16 @@ struct Grouped {
 }
 
 enum Tree {
-    Leaf(IndexMap<String, Vec<Value>>),
-    Branch(IndexMap<String, Grouped>),
+    Leaf(IndexMap<Key, Vec<Value>>),
+    Branch(IndexMap<Key, Grouped>),
 }
 
 impl Grouped {
-    fn empty(values: Vec<Value>, config: &nu_protocol::Config) -> Self {
+    fn empty(values: Vec<Value>, raw_keys: bool, config: &nu_protocol::Config) -> Self {
         let mut groups = IndexMap::<_, Vec<_>>::new();
 
         for value in values.into_iter() {
-            let key = value.to_abbreviated_string(config);
+            let key = Key::from_ref(&value, raw_keys, config);
             groups.entry(key).or_default().push(value);
         }
 
@@ -251,15 +676,33 @@ impl Grouped {
     fn new(
         grouper: &Value,
         values: Vec<Value>,
+        raw_keys: bool,
+        binning: Option<&Binning>,
         config: &nu_protocol::Config,
         engine_state: &EngineState,
         stack: &mut Stack,
     ) -> Result<Self, ShellError> {
         let span = grouper.span();
         let groups = match grouper {
-            Value::CellPath { val, .. } => group_cell_path(val.clone(), values, config)?,
+            Value::CellPath { val, .. } => match binning {
+                Some(binning) => group_bins(val.clone(), values, raw_keys, binning, span, config)?,
+                None => group_cell_path(val.clone(), values, raw_keys, config)?,
+            },
             Value::Closure { val, .. } => {
-                group_closure(values, span, Closure::clone(val), engine_state, stack)?
+                if binning.is_some() {
+                    return Err(ShellError::TypeMismatch {
+                        err_message: "--bins/--every require a cell-path grouper".to_string(),
+                        span,
+                    });
+                }
+                group_closure(
+                    values,
+                    span,
+                    Closure::clone(val),
+                    raw_keys,
+                    engine_state,
+                    stack,
+                )?
             }
             _ => {
                 return Err(ShellError::TypeMismatch {
@@ -278,6 +721,7 @@ impl Grouped {
     fn subgroup(
         &mut self,
         grouper: &Value,
+        raw_keys: bool,
         config: &nu_protocol::Config,
         engine_state: &EngineState,
         stack: &mut Stack,
@@ -287,7 +731,15 @@ impl Grouped {
                 let gv = std::mem::take(gv);
                 gv.into_iter()
                     .map(|(key, values)| -> Result<_, ShellError> {
-                        let leaf = Self::new(grouper, values, config, engine_state, stack)?;
+                        let leaf = Self::new(
+                            grouper,
+                            values,
+                            raw_keys,
+                            None,
+                            config,
+                            engine_state,
+                            stack,
+                        )?;
                         Ok((key, leaf))
                     })
                     .collect::<Result<IndexMap<_, _>, _>>()?
@@ -295,7 +747,7 @@ impl Grouped {
             Tree::Branch(gg) => {
                 let mut gg = std::mem::take(gg);
                 for v in gg.values_mut() {
-                    v.subgroup(grouper, config, engine_state, stack)?;
+                    v.subgroup(grouper, raw_keys, config, engine_state, stack)?;
                 }
                 gg
             }
@@ -304,55 +756,154 @@ impl Grouped {
         Ok(())
     }
 
-    fn into_table(self, head: Span) -> Value {
-        self._into_table(head, 0)
+    /// The total number of input items contained in this group, including nested subgroups.
+    fn item_count(&self) -> usize {
+        match &self.groups {
+            Tree::Leaf(leaf) => leaf.values().map(Vec::len).sum(),
+            Tree::Branch(branch) => branch.values().map(Grouped::item_count).sum(),
+        }
+    }
+
+    /// Sorts and truncates the groups at every level of the tree, per `--sort-by`/`--reverse`/
+    /// `--take`.
+    fn sort_and_take(
+        &mut self,
+        sort_by: Option<SortBy>,
+        reverse: bool,
+        take: Option<usize>,
+        config: &nu_protocol::Config,
+    ) {
+        match &mut self.groups {
+            Tree::Leaf(leaf) => {
+                if let Some(sort_by) = sort_by {
+                    leaf.sort_by(|a_key, a_values, b_key, b_values| match sort_by {
+                        SortBy::Key => a_key.cmp_for_sort(b_key, config),
+                        SortBy::Count => a_values.len().cmp(&b_values.len()),
+                    });
+                }
+                if reverse {
+                    leaf.reverse();
+                }
+                if let Some(take) = take {
+                    leaf.truncate(take);
+                }
+            }
+            Tree::Branch(branch) => {
+                for group in branch.values_mut() {
+                    group.sort_and_take(sort_by, reverse, take, config);
+                }
+                if let Some(sort_by) = sort_by {
+                    branch.sort_by(|a_key, a_group, b_key, b_group| match sort_by {
+                        SortBy::Key => a_key.cmp_for_sort(b_key, config),
+                        SortBy::Count => a_group.item_count().cmp(&b_group.item_count()),
+                    });
+                }
+                if reverse {
+                    branch.reverse();
+                }
+                if let Some(take) = take {
+                    branch.truncate(take);
+                }
+            }
+        }
+    }
+
+    fn into_table(
+        self,
+        head: Span,
+        aggregators: Option<&[Aggregator]>,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+    ) -> Result<Value, ShellError> {
+        let rows = self._into_table(head, 0, aggregators, engine_state, stack)?;
+        Ok(rows
             .into_iter()
-            .map(|row| row.into_iter().rev().collect::<Record>().into_value(head))
+            .map(|row| row.into_iter().collect::<Record>().into_value(head))
             .collect::<Vec<_>>()
-            .into_value(head)
+            .into_value(head))
     }
 
-    fn _into_table(self, head: Span, index: usize) -> Vec<Record> {
+    /// Builds each row grouper-columns-first (outermost grouper to innermost), followed by the
+    /// leaf's aggregate/items columns in the order the caller wrote them — so the row is already
+    /// in final column order and `into_table` doesn't need to reverse anything.
+    fn _into_table(
+        self,
+        head: Span,
+        index: usize,
+        aggregators: Option<&[Aggregator]>,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+    ) -> Result<Vec<Vec<(String, Value)>>, ShellError> {
         let grouper = self.grouper.unwrap_or_else(|| format!("group{index}"));
         match self.groups {
             Tree::Leaf(leaf) => leaf
                 .into_iter()
                 .map(|(group, values)| {
-                    [
-                        ("items".to_string(), values.into_value(head)),
-                        (grouper.clone(), group.into_value(head)),
-                    ]
-                    .into_iter()
-                    .collect()
+                    let mut row = vec![(grouper.clone(), group.into_value(head))];
+                    if let Some(aggregators) = aggregators {
+                        let aggregate =
+                            run_aggregators(aggregators, &values, head, engine_state, stack)?;
+                        row.extend(aggregate);
+                    } else {
+                        row.push(("items".into(), values.into_value(head)));
+                    }
+                    Ok(row)
                 })
-                .collect::<Vec<Record>>(),
+                .collect::<Result<Vec<_>, ShellError>>(),
             Tree::Branch(branch) => branch
                 .into_iter()
-                .flat_map(|(group, items)| {
-                    let mut inner = items._into_table(head, index + 1);
-                    for row in &mut inner {
-                        row.insert(grouper.clone(), group.clone().into_value(head));
-                    }
-                    inner
+                .map(|(group, items)| {
+                    let inner =
+                        items._into_table(head, index + 1, aggregators, engine_state, stack)?;
+                    Ok(inner
+                        .into_iter()
+                        .map(|row| {
+                            let mut row = row;
+                            row.insert(0, (grouper.clone(), group.clone().into_value(head)));
+                            row
+                        })
+                        .collect::<Vec<_>>())
                 })
-                .collect(),
+                .collect::<Result<Vec<Vec<_>>, ShellError>>()
+                .map(|rows| rows.into_iter().flatten().collect()),
         }
     }
 
-    fn into_record(self, head: Span) -> Value {
+    fn into_record(
+        self,
+        head: Span,
+        aggregators: Option<&[Aggregator]>,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+    ) -> Result<Value, ShellError> {
+        let config = engine_state.get_config();
         match self.groups {
-            Tree::Leaf(leaf) => Value::record(
-                leaf.into_iter()
-                    .map(|(k, v)| (k, v.into_value(head)))
-                    .collect(),
-                head,
-            ),
+            Tree::Leaf(leaf) => {
+                let record = leaf
+                    .into_iter()
+                    .map(|(k, values)| -> Result<_, ShellError> {
+                        let value = if let Some(aggregators) = aggregators {
+                            Value::record(
+                                run_aggregators(aggregators, &values, head, engine_state, stack)?,
+                                head,
+                            )
+                        } else {
+                            values.into_value(head)
+                        };
+                        Ok((k.to_label_string(config), value))
+                    })
+                    .collect::<Result<Record, ShellError>>()?;
+                Ok(Value::record(record, head))
+            }
             Tree::Branch(branch) => {
                 let values = branch
                     .into_iter()
-                    .map(|(k, v)| (k, v.into_record(head)))
-                    .collect();
-                Value::record(values, head)
+                    .map(|(k, v)| -> Result<_, ShellError> {
+                        let label = k.to_label_string(config);
+                        Ok((label, v.into_record(head, aggregators, engine_state, stack)?))
+                    })
+                    .collect::<Result<Record, ShellError>>()?;
+                Ok(Value::record(values, head))
             }
         }
     }
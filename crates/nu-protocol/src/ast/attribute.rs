@@ -1,7 +1,26 @@
 use super::Expression;
-use crate::Span;
+use crate::{Record, ShellError, Span, Value};
 use serde::{Deserialize, Serialize};
 
+/// A single `@name ...` attribute evaluated above a `def`, e.g. the `@example` in:
+///
+/// ```nu
+/// @example "add one" { 1 | inc }
+/// def inc [] { $in + 1 }
+/// ```
+///
+/// `expr` is the call expression (`example "add one" { 1 | inc }` above); evaluating it runs
+/// whatever `attr <name>` command matches, by the same call convention as any other command
+/// call. The convention third parties should follow when registering their own attributes:
+///
+/// - Name the command `attr <name>` (mirroring the built-ins under
+///   `nu-cmd-lang::core_commands::attr`, e.g. `AttrExample`, `AttrCategory`, `AttrDeprecated`,
+///   `AttrVersion`, `AttrAuthor`, `AttrSearchTerms`).
+/// - Accept no pipeline input and return a record describing the attribute (its fields are
+///   whatever that attribute needs downstream: `AttrCategory` returns `{ category: string }`,
+///   `AttrDeprecated` returns `{ message?, since?, remove_in? }`, etc).
+/// - Mark `is_const(&self) -> bool { true }` and implement `run_const`, since attributes are
+///   evaluated before the command they decorate exists to be called normally.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attribute {
     pub operator: Span,
@@ -13,3 +32,93 @@ pub struct AttributeBlock {
     pub attributes: Vec<Attribute>,
     pub item: Box<Expression>,
 }
+
+/// The metadata a custom command collects from its `@category`/`@deprecated`/`@version`/
+/// `@author`/`@search-terms` attributes, once each `Attribute::expr` in an `AttributeBlock` has
+/// been evaluated to the record its `attr <name>` command returns.
+///
+/// This only folds those already-evaluated records together; it doesn't itself run the call
+/// expressions (that needs an `EngineState`/`Stack`, which live in `nu-engine`) and nothing in
+/// this checkout yet calls it from the `def` declaration path (that's `nu-parser`/`nu-engine`
+/// territory, neither of which exists in this checkout). `attr example`'s attribute is handled
+/// separately today and isn't folded in here, since its record is consumed by `help` directly
+/// rather than attached to the `Signature`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandAttributes {
+    pub search_terms: Vec<String>,
+    pub category: Option<String>,
+    pub deprecated: Option<DeprecationInfo>,
+    pub version: Option<String>,
+    pub author: Option<AuthorInfo>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeprecationInfo {
+    pub message: Option<String>,
+    pub since: Option<String>,
+    pub remove_in: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorInfo {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+impl CommandAttributes {
+    /// Folds one already-evaluated attribute record into `self`, keyed by the attribute's name as
+    /// written (`"category"` for `@category`, not the `attr category` command name). Returns a
+    /// `ShellError` if `name` is a known attribute but `record` is missing a field that
+    /// attribute's command always includes (`category`'s `category`, `version`'s `version`,
+    /// `search-terms`'s `search_terms`, `author`'s `name`). `deprecated`'s fields are all
+    /// optional in `attr deprecated`'s own signature, so there's nothing to require here.
+    pub fn merge(&mut self, name: &str, record: Record, span: Span) -> Result<(), ShellError> {
+        let string_field = |record: &Record, field: &str| -> Option<String> {
+            record
+                .get(field)
+                .and_then(|value| value.as_str().ok())
+                .map(str::to_string)
+        };
+        let required_string_field = |record: &Record, field: &str| -> Result<String, ShellError> {
+            string_field(record, field).ok_or_else(|| ShellError::TypeMismatch {
+                err_message: format!("`@{name}` record is missing its `{field}` field"),
+                span,
+            })
+        };
+
+        match name {
+            "category" => {
+                self.category = Some(required_string_field(&record, "category")?);
+            }
+            "version" => {
+                self.version = Some(required_string_field(&record, "version")?);
+            }
+            "search-terms" => {
+                let Some(Value::List { vals, .. }) = record.get("search_terms") else {
+                    return Err(ShellError::TypeMismatch {
+                        err_message: "`@search-terms` record is missing its `search_terms` field"
+                            .to_string(),
+                        span,
+                    });
+                };
+                self.search_terms
+                    .extend(vals.iter().filter_map(|v| v.as_str().ok().map(str::to_string)));
+            }
+            "deprecated" => {
+                self.deprecated = Some(DeprecationInfo {
+                    message: string_field(&record, "message"),
+                    since: string_field(&record, "since"),
+                    remove_in: string_field(&record, "remove_in"),
+                });
+            }
+            "author" => {
+                self.author = Some(AuthorInfo {
+                    name: required_string_field(&record, "name")?,
+                    email: string_field(&record, "email"),
+                });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
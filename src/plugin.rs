@@ -1,6 +1,6 @@
 use crate::{Args, CommandConfig, ReturnValue, ShellError, Value};
-use serde::{Deserialize, Serialize};
-use std::io;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
 
 pub trait Plugin {
     fn config(&mut self) -> Result<CommandConfig, ShellError>;
@@ -14,6 +14,13 @@ pub trait Plugin {
     fn filter(&mut self, input: Value) -> Result<Vec<ReturnValue>, ShellError> {
         Err(ShellError::string("`filter` not implemented in plugin"))
     }
+    /// Called once after the last `filter` call, to flush any state `filter` buffered (e.g. a
+    /// windowed aggregator's trailing window, or a `group-by`-like accumulator's groups).
+    /// Defaults to emitting nothing, so plugins that don't buffer don't need to implement it.
+    #[allow(unused)]
+    fn end_filter(&mut self) -> Result<Vec<ReturnValue>, ShellError> {
+        Ok(Vec::new())
+    }
     #[allow(unused)]
     fn sink(&mut self, args: Args, input: Vec<Value>) {}
 
@@ -22,44 +29,93 @@ pub trait Plugin {
     }
 }
 
+/// The wire encoding used for every `NuCommand`/response after the initial handshake line.
+/// `Json` is the original line-delimited encoding, kept as the default so existing plugins
+/// that only understand it keep working unmodified. `MsgPack` frames each message as a
+/// little-endian `u32` byte length followed by that many bytes of MessagePack, which is
+/// binary-safe (no re-encoding every `Value` as UTF-8, no breakage on embedded newlines).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginEncoding {
+    Json,
+    MsgPack,
+}
+
+impl PluginEncoding {
+    /// The single line `serve_plugin_with_encoding` writes before entering its read loop when
+    /// `self` isn't `Json` (see its doc comment for why `Json` skips this), and the line nushell
+    /// reads first when it starts a plugin that does write it, so both sides agree on how every
+    /// message after it is framed.
+    fn handshake_line(self) -> &'static str {
+        match self {
+            PluginEncoding::Json => "json",
+            PluginEncoding::MsgPack => "msgpack",
+        }
+    }
+
+    fn from_handshake_line(line: &str) -> Self {
+        match line.trim_end() {
+            "msgpack" => PluginEncoding::MsgPack,
+            _ => PluginEncoding::Json,
+        }
+    }
+}
+
+/// Upper bound on a single `MsgPack`-framed message's declared length, so a corrupt or
+/// adversarial length prefix can't force an unbounded allocation before we even try to decode.
+const MAX_FRAME_LEN: u32 = 64 * 1024 * 1024;
+
+/// Starts the plugin's read/respond loop using the original line-delimited JSON encoding, for
+/// plugins that don't need `serve_plugin_with_encoding`'s binary-safe framing. Writes nothing
+/// before reading the first command, exactly like before this file gained msgpack support, so
+/// existing JSON-only plugins and hosts keep working unmodified.
 pub fn serve_plugin(plugin: &mut dyn Plugin) {
+    serve_plugin_with_encoding(plugin, PluginEncoding::Json)
+}
+
+/// Starts the plugin's read/respond loop, framing every `NuCommand`/response under `encoding`.
+/// `encoding` is announced to nushell with a handshake line before the loop starts, *except* for
+/// `PluginEncoding::Json`: that's the original, preamble-free wire behavior, so plugins calling
+/// `serve_plugin` (which always passes `Json`) see no unsolicited output, while plugins that
+/// opt into `MsgPack` still need the line so nushell knows to switch its own decoder.
+pub fn serve_plugin_with_encoding(plugin: &mut dyn Plugin, encoding: PluginEncoding) {
+    if encoding != PluginEncoding::Json {
+        println!("{}", encoding.handshake_line());
+    }
+
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
     loop {
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
-                let command = serde_json::from_str::<NuCommand>(&input);
-                match command {
-                    Ok(NuCommand::config) => {
-                        send_response(plugin.config());
-                    }
-                    Ok(NuCommand::begin_filter { params }) => {
-                        let _ = plugin.begin_filter(params);
-                    }
-                    Ok(NuCommand::filter { params }) => {
-                        send_response(plugin.filter(params));
-                    }
-                    Ok(NuCommand::sink { params }) => {
-                        plugin.sink(params.0, params.1);
-                        break;
-                    }
-                    Ok(NuCommand::quit) => {
-                        plugin.quit();
-                        break;
-                    }
-                    e => {
-                        send_response(ShellError::string(format!(
-                            "Could not handle plugin message: {} {:?}",
-                            input, e
-                        )));
-                        break;
-                    }
-                }
+        let command = match read_message::<NuCommand>(&mut reader, encoding) {
+            Ok(Some(command)) => command,
+            Ok(None) => break,
+            Err(err) => {
+                send_response(
+                    encoding,
+                    ShellError::string(format!("Could not handle plugin message: {err}")),
+                );
+                break;
+            }
+        };
+
+        match command {
+            NuCommand::config => {
+                send_response(encoding, plugin.config());
+            }
+            NuCommand::begin_filter { params } => {
+                let _ = plugin.begin_filter(params);
+            }
+            NuCommand::filter { params } => {
+                send_response(encoding, plugin.filter(params));
             }
-            e => {
-                send_response(ShellError::string(format!(
-                    "Could not handle plugin message: {:?}",
-                    e,
-                )));
+            NuCommand::end_filter => {
+                send_response(encoding, plugin.end_filter());
+            }
+            NuCommand::sink { params } => {
+                plugin.sink(params.0, params.1);
+                break;
+            }
+            NuCommand::quit => {
+                plugin.quit();
                 break;
             }
         }
@@ -82,11 +138,64 @@ impl<T> JsonRpc<T> {
     }
 }
 
-fn send_response<T: Serialize>(result: T) {
+/// Reads one message under `encoding`, or `Ok(None)` at end-of-stream. `serve_plugin_with_encoding`
+/// uses it to read each `NuCommand`.
+fn read_message<T: DeserializeOwned>(
+    reader: &mut impl BufRead,
+    encoding: PluginEncoding,
+) -> io::Result<Option<T>> {
+    match encoding {
+        PluginEncoding::Json => {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let message = serde_json::from_str(line.trim_end())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            Ok(Some(message))
+        }
+        PluginEncoding::MsgPack => {
+            let mut len_bytes = [0u8; 4];
+            match reader.read_exact(&mut len_bytes) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(err) => return Err(err),
+            }
+            let len = u32::from_le_bytes(len_bytes);
+            if len > MAX_FRAME_LEN {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("frame of {len} bytes exceeds the {MAX_FRAME_LEN} byte limit"),
+                ));
+            }
+            let mut body = vec![0u8; len as usize];
+            reader.read_exact(&mut body)?;
+            let message = rmp_serde::from_slice(&body)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            Ok(Some(message))
+        }
+    }
+}
+
+fn send_response<T: Serialize>(encoding: PluginEncoding, result: T) {
     let response = JsonRpc::new("response", result);
-    let response_raw = serde_json::to_string(&response).unwrap();
-    println!("{}", response_raw);
+    match encoding {
+        PluginEncoding::Json => {
+            let response_raw = serde_json::to_string(&response).unwrap();
+            println!("{response_raw}");
+        }
+        PluginEncoding::MsgPack => {
+            let body = rmp_serde::to_vec_named(&response).unwrap();
+            let len = u32::try_from(body.len()).unwrap();
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            stdout.write_all(&len.to_le_bytes()).unwrap();
+            stdout.write_all(&body).unwrap();
+            stdout.flush().unwrap();
+        }
+    }
 }
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "method")]
 #[allow(non_camel_case_types)]
@@ -94,6 +203,7 @@ pub enum NuCommand {
     config,
     begin_filter { params: Args },
     filter { params: Value },
+    end_filter,
     sink { params: (Args, Vec<Value>) },
     quit,
 }